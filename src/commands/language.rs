@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+
+use crate::{
+    db,
+    locale::{self, t},
+};
+
+use super::{Command, CommandContext};
+
+pub struct LanguageCommand;
+
+#[async_trait]
+impl Command for LanguageCommand {
+    async fn execute(&self, ctx: CommandContext<'_>) -> anyhow::Result<String> {
+        let lang = ctx.args.to_lowercase();
+        if lang.is_empty() {
+            return Ok(t(&ctx.lang, "language_usage", &[]));
+        }
+
+        if !locale::is_supported(&lang) {
+            let langs = locale::supported_langs().join(", ");
+            return Ok(t(
+                &ctx.lang,
+                "language_unknown",
+                &[("lang", &lang), ("langs", &langs)],
+            ));
+        }
+
+        if !db::set_language(ctx.pool, ctx.chat_id, &lang).await? {
+            return Ok(t(&ctx.lang, "language_requires_subscription", &[]));
+        }
+        Ok(t(&lang, "language_set", &[("lang", &lang)]))
+    }
+}
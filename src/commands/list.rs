@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+
+use crate::{db, locale::t};
+
+use super::{Command, CommandContext};
+
+pub struct ListCommand;
+
+#[async_trait]
+impl Command for ListCommand {
+    async fn execute(&self, ctx: CommandContext<'_>) -> anyhow::Result<String> {
+        let keywords = db::list_watches(ctx.pool, ctx.chat_id).await?;
+        Ok(if keywords.is_empty() {
+            t(&ctx.lang, "watch_list_empty", &[])
+        } else {
+            t(&ctx.lang, "watch_list", &[("keywords", &keywords.join(", "))])
+        })
+    }
+}
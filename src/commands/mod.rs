@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+mod language;
+mod list;
+mod subscribe;
+mod unsubscribe;
+mod unwatch;
+mod watch;
+
+use language::LanguageCommand;
+use list::ListCommand;
+use subscribe::SubscribeCommand;
+use unsubscribe::UnsubscribeCommand;
+use unwatch::UnwatchCommand;
+use watch::WatchCommand;
+
+use crate::db;
+
+/// Everything a `Command` needs to do its job, without reaching back into
+/// the update loop for it.
+pub struct CommandContext<'a> {
+    pub pool: &'a PgPool,
+    pub chat_id: i64,
+    pub args: String,
+    pub lang: String,
+}
+
+/// A single bot command, addressable by name in the `Registry`.
+#[async_trait]
+pub trait Command: Send + Sync {
+    async fn execute(&self, ctx: CommandContext<'_>) -> anyhow::Result<String>;
+}
+
+/// Parses incoming messages and routes them to the matching `Command`.
+pub struct Registry {
+    prefix: char,
+    commands: HashMap<&'static str, Box<dyn Command>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        let mut commands: HashMap<&'static str, Box<dyn Command>> = HashMap::new();
+        commands.insert("subscribe", Box::new(SubscribeCommand));
+        commands.insert("unsubscribe", Box::new(UnsubscribeCommand));
+        commands.insert("watch", Box::new(WatchCommand));
+        commands.insert("unwatch", Box::new(UnwatchCommand));
+        commands.insert("list", Box::new(ListCommand));
+        commands.insert("language", Box::new(LanguageCommand));
+
+        Registry {
+            prefix: '/',
+            commands,
+        }
+    }
+
+    /// Strips the command prefix and splits the rest on the first run of
+    /// whitespace, returning `(name, args)`. Returns `None` if `text`
+    /// doesn't start with the prefix.
+    fn parse<'a>(&self, text: &'a str) -> Option<(&'a str, &'a str)> {
+        let rest = text.strip_prefix(self.prefix)?;
+        Some(match rest.split_once(char::is_whitespace) {
+            Some((name, args)) => (name, args.trim()),
+            None => (rest, ""),
+        })
+    }
+
+    /// Looks up and runs the command for `text`, if any. Returns the reply
+    /// text to send back, or `None` if `text` isn't a recognized command.
+    pub async fn dispatch(
+        &self,
+        pool: &PgPool,
+        chat_id: i64,
+        text: &str,
+    ) -> anyhow::Result<Option<String>> {
+        let Some((name, args)) = self.parse(text) else {
+            return Ok(None);
+        };
+        let Some(command) = self.commands.get(name) else {
+            return Ok(None);
+        };
+
+        let lang = db::get_language(pool, chat_id).await?;
+        let ctx = CommandContext {
+            pool,
+            chat_id,
+            args: args.to_string(),
+            lang,
+        };
+        command.execute(ctx).await.map(Some)
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
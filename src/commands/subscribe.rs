@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+
+use crate::{db, locale::t};
+
+use super::{Command, CommandContext};
+
+pub struct SubscribeCommand;
+
+#[async_trait]
+impl Command for SubscribeCommand {
+    async fn execute(&self, ctx: CommandContext<'_>) -> anyhow::Result<String> {
+        db::add_subscriber(ctx.pool, ctx.chat_id).await?;
+        Ok(t(&ctx.lang, "subscribed", &[]))
+    }
+}
@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+
+use crate::{db, locale::t};
+
+use super::{Command, CommandContext};
+
+pub struct UnsubscribeCommand;
+
+#[async_trait]
+impl Command for UnsubscribeCommand {
+    async fn execute(&self, ctx: CommandContext<'_>) -> anyhow::Result<String> {
+        db::remove_subscriber(ctx.pool, ctx.chat_id).await?;
+        Ok(t(&ctx.lang, "unsubscribed", &[]))
+    }
+}
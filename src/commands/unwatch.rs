@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+
+use crate::{db, locale::t};
+
+use super::{Command, CommandContext};
+
+pub struct UnwatchCommand;
+
+#[async_trait]
+impl Command for UnwatchCommand {
+    async fn execute(&self, ctx: CommandContext<'_>) -> anyhow::Result<String> {
+        let keyword = ctx.args.trim();
+        if keyword.is_empty() {
+            return Ok(t(&ctx.lang, "unwatch_usage", &[]));
+        }
+
+        // Kept consistent with `watch`: keywords are stored verbatim.
+        db::remove_watch(ctx.pool, ctx.chat_id, keyword).await?;
+        Ok(t(&ctx.lang, "watch_removed", &[("keyword", keyword)]))
+    }
+}
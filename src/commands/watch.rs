@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+
+use crate::{db, locale::t};
+
+use super::{Command, CommandContext};
+
+pub struct WatchCommand;
+
+#[async_trait]
+impl Command for WatchCommand {
+    async fn execute(&self, ctx: CommandContext<'_>) -> anyhow::Result<String> {
+        let keyword = ctx.args.trim();
+        if keyword.is_empty() {
+            return Ok(t(&ctx.lang, "watch_usage", &[]));
+        }
+
+        // Stored verbatim: `matching` already case-folds plain substrings
+        // and forces `(?i)` on regex bodies, and lowercasing here would
+        // mangle `AND`/`NOT` and regex escapes like `\B`/`\W`.
+        db::add_watch(ctx.pool, ctx.chat_id, keyword).await?;
+        Ok(t(&ctx.lang, "watch_added", &[("keyword", keyword)]))
+    }
+}
@@ -0,0 +1,93 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use sqlx::{postgres::PgListener, PgPool};
+use tokio::sync::RwLock;
+
+use crate::db;
+
+/// Shared, hot-reloadable view of subscribers, their language preference,
+/// and their watch lists, kept in sync with the database via Postgres
+/// `LISTEN`/`NOTIFY` instead of being re-read from scratch on every poll.
+#[derive(Clone)]
+pub struct ConfigCache {
+    subscribers: Arc<RwLock<HashSet<i64>>>,
+    languages: Arc<RwLock<HashMap<i64, String>>>,
+    watches: Arc<RwLock<HashMap<i64, Vec<String>>>>,
+}
+
+impl ConfigCache {
+    pub async fn load(pool: &PgPool) -> anyhow::Result<Self> {
+        let subscribers = db::get_subscribers(pool).await?;
+        let languages = db::get_languages(pool).await?;
+        let watches = db::get_watches(pool).await?;
+        Ok(ConfigCache {
+            subscribers: Arc::new(RwLock::new(subscribers)),
+            languages: Arc::new(RwLock::new(languages)),
+            watches: Arc::new(RwLock::new(watches)),
+        })
+    }
+
+    pub async fn subscribers(&self) -> HashSet<i64> {
+        self.subscribers.read().await.clone()
+    }
+
+    pub async fn languages(&self) -> HashMap<i64, String> {
+        self.languages.read().await.clone()
+    }
+
+    pub async fn watches(&self) -> HashMap<i64, Vec<String>> {
+        self.watches.read().await.clone()
+    }
+
+    async fn refresh(&self, pool: &PgPool) -> anyhow::Result<()> {
+        let subscribers = db::get_subscribers(pool).await?;
+        let languages = db::get_languages(pool).await?;
+        let watches = db::get_watches(pool).await?;
+        *self.subscribers.write().await = subscribers;
+        *self.languages.write().await = languages;
+        *self.watches.write().await = watches;
+        Ok(())
+    }
+}
+
+/// Spawns a background task that listens for `subscribers_changed` and
+/// `watches_changed` notifications (fired by triggers on the `subscribers`
+/// and `watches` tables) and refreshes `cache` whenever either one fires.
+/// This lets other processes — another bot instance, or an admin dashboard —
+/// mutate config and have it take effect before the next poll.
+pub fn spawn_listener(pool: PgPool, cache: ConfigCache) {
+    tokio::spawn(async move {
+        let mut listener = match PgListener::connect_with(&pool).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                eprintln!("Error connecting config listener: {:?}", e);
+                return;
+            }
+        };
+
+        if let Err(e) = listener
+            .listen_all(["subscribers_changed", "watches_changed"])
+            .await
+        {
+            eprintln!("Error subscribing to config notifications: {:?}", e);
+            return;
+        }
+
+        loop {
+            match listener.recv().await {
+                Ok(notification) => {
+                    println!("Config changed: {}", notification.channel());
+                    if let Err(e) = cache.refresh(&pool).await {
+                        eprintln!("Error refreshing config cache: {:?}", e);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Error receiving config notification: {:?}", e);
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,127 @@
+use std::collections::{HashMap, HashSet};
+
+use sqlx::PgPool;
+
+pub async fn get(pool: &PgPool, key: &str, default: String) -> anyhow::Result<String> {
+    let row: Option<(String,)> = sqlx::query_as("SELECT value FROM settings WHERE key = $1")
+        .bind(key)
+        .fetch_optional(pool)
+        .await?;
+
+    match row {
+        Some(x) => Ok(x.0),
+        None => Ok(default),
+    }
+}
+
+pub async fn set(pool: &PgPool, key: &str, value: &str) -> anyhow::Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO settings (key, value) VALUES ($1, $2)
+        ON CONFLICT (key) DO UPDATE
+        SET value = $2
+        "#,
+    )
+    .bind(key)
+    .bind(value)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn get_subscribers(pool: &PgPool) -> anyhow::Result<HashSet<i64>> {
+    let subscribers = sqlx::query_as("SELECT chat_id FROM subscribers")
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|row: (i64,)| row.0)
+        .collect::<HashSet<i64>>();
+    Ok(subscribers)
+}
+
+pub async fn add_subscriber(pool: &PgPool, chat_id: i64) -> anyhow::Result<()> {
+    sqlx::query("INSERT INTO subscribers (chat_id) VALUES ($1)")
+        .bind(chat_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn remove_subscriber(pool: &PgPool, chat_id: i64) -> anyhow::Result<()> {
+    sqlx::query("DELETE FROM subscribers WHERE chat_id = $1")
+        .bind(chat_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn get_watches(pool: &PgPool) -> anyhow::Result<HashMap<i64, Vec<String>>> {
+    let rows: Vec<(i64, String)> = sqlx::query_as("SELECT chat_id, keyword FROM watches")
+        .fetch_all(pool)
+        .await?;
+
+    let mut watches: HashMap<i64, Vec<String>> = HashMap::new();
+    for (chat_id, keyword) in rows {
+        watches.entry(chat_id).or_default().push(keyword);
+    }
+    Ok(watches)
+}
+
+pub async fn list_watches(pool: &PgPool, chat_id: i64) -> anyhow::Result<Vec<String>> {
+    let rows: Vec<(String,)> =
+        sqlx::query_as("SELECT keyword FROM watches WHERE chat_id = $1 ORDER BY keyword")
+            .bind(chat_id)
+            .fetch_all(pool)
+            .await?;
+    Ok(rows.into_iter().map(|row| row.0).collect())
+}
+
+pub async fn add_watch(pool: &PgPool, chat_id: i64, keyword: &str) -> anyhow::Result<()> {
+    sqlx::query("INSERT INTO watches (chat_id, keyword) VALUES ($1, $2) ON CONFLICT DO NOTHING")
+        .bind(chat_id)
+        .bind(keyword)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn remove_watch(pool: &PgPool, chat_id: i64, keyword: &str) -> anyhow::Result<()> {
+    sqlx::query("DELETE FROM watches WHERE chat_id = $1 AND keyword = $2")
+        .bind(chat_id)
+        .bind(keyword)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn get_language(pool: &PgPool, chat_id: i64) -> anyhow::Result<String> {
+    let row: Option<(String,)> =
+        sqlx::query_as("SELECT language FROM subscribers WHERE chat_id = $1")
+            .bind(chat_id)
+            .fetch_optional(pool)
+            .await?;
+    Ok(row
+        .map(|row| row.0)
+        .unwrap_or_else(|| crate::locale::DEFAULT_LANG.to_string()))
+}
+
+pub async fn get_languages(pool: &PgPool) -> anyhow::Result<HashMap<i64, String>> {
+    let rows: Vec<(i64, String)> = sqlx::query_as("SELECT chat_id, language FROM subscribers")
+        .fetch_all(pool)
+        .await?;
+    Ok(rows.into_iter().collect())
+}
+
+/// Updates an existing subscriber's language. Returns `false` without
+/// writing anything if `chat_id` isn't subscribed yet, rather than
+/// creating a `subscribers` row for them — `language` lives on that
+/// table, but setting it shouldn't silently enroll someone who never ran
+/// `/subscribe`.
+pub async fn set_language(pool: &PgPool, chat_id: i64, lang: &str) -> anyhow::Result<bool> {
+    let result = sqlx::query("UPDATE subscribers SET language = $2 WHERE chat_id = $1")
+        .bind(chat_id)
+        .bind(lang)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
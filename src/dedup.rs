@@ -0,0 +1,83 @@
+use std::time::Duration;
+
+use sqlx::PgPool;
+use ttl_cache::TtlCache;
+
+const TTL: Duration = Duration::from_secs(30 * 60);
+const CAPACITY: usize = 2048;
+const PRUNE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Tracks which Reddit submissions have already been broadcast. Backed by
+/// an in-memory TTL cache for the hot path, with `seen_submissions` behind
+/// it so a restart doesn't lose the window and re-send (or, worse, drop)
+/// posts.
+pub struct SeenCache {
+    cache: TtlCache<Vec<u8>, ()>,
+}
+
+impl SeenCache {
+    /// Claims `reddit_id` for this instance, returning `true` only if it
+    /// wasn't already seen. The local cache is just a fast path to skip the
+    /// round-trip for ids this process has already checked or loaded at
+    /// startup, whichever instance claimed them; the database is
+    /// the arbiter across instances, via `INSERT ... ON CONFLICT DO NOTHING
+    /// RETURNING`, so two bot instances racing on the same submission never
+    /// both broadcast it.
+    pub async fn try_claim(&mut self, pool: &PgPool, reddit_id: Vec<u8>) -> anyhow::Result<bool> {
+        if self.cache.contains_key(&reddit_id) {
+            return Ok(false);
+        }
+
+        let claimed: Option<(Vec<u8>,)> = sqlx::query_as(
+            "INSERT INTO seen_submissions (reddit_id, seen_at) VALUES ($1, now())
+             ON CONFLICT (reddit_id) DO NOTHING
+             RETURNING reddit_id",
+        )
+        .bind(&reddit_id)
+        .fetch_optional(pool)
+        .await?;
+
+        self.cache.insert(reddit_id, (), TTL);
+        Ok(claimed.is_some())
+    }
+}
+
+/// Rehydrates the cache from `seen_submissions`, discarding anything
+/// already older than the TTL, and returns the greatest reddit id found so
+/// the caller can reseed `last_reddit_id` too.
+pub async fn load(pool: &PgPool) -> anyhow::Result<(SeenCache, Option<Vec<u8>>)> {
+    let rows: Vec<(Vec<u8>,)> = sqlx::query_as(
+        "SELECT reddit_id FROM seen_submissions WHERE seen_at > now() - interval '30 minutes'",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut cache = TtlCache::new(CAPACITY);
+    let mut last_reddit_id: Option<Vec<u8>> = None;
+    for (reddit_id,) in rows {
+        if last_reddit_id.as_ref().map_or(true, |last| &reddit_id > last) {
+            last_reddit_id = Some(reddit_id.clone());
+        }
+        cache.insert(reddit_id, (), TTL);
+    }
+
+    Ok((SeenCache { cache }, last_reddit_id))
+}
+
+/// Periodically deletes rows older than the TTL so `seen_submissions`
+/// doesn't grow forever.
+pub fn spawn_pruner(pool: PgPool) {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(PRUNE_INTERVAL).await;
+            if let Err(e) = sqlx::query(
+                "DELETE FROM seen_submissions WHERE seen_at < now() - interval '30 minutes'",
+            )
+            .execute(&pool)
+            .await
+            {
+                eprintln!("Error pruning seen_submissions: {:?}", e);
+            }
+        }
+    });
+}
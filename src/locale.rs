@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+
+pub const DEFAULT_LANG: &str = "en";
+
+static STRINGS: Lazy<HashMap<&'static str, HashMap<&'static str, &'static str>>> = Lazy::new(|| {
+    let mut langs = HashMap::new();
+
+    let mut en = HashMap::new();
+    en.insert("subscribed", "Subscribed to mechmarket");
+    en.insert("unsubscribed", "Unsubscribed from mechmarket");
+    en.insert("watch_usage", "Usage: /watch <keyword>");
+    en.insert("watch_added", "Watching for \"{keyword}\"");
+    en.insert("unwatch_usage", "Usage: /unwatch <keyword>");
+    en.insert("watch_removed", "Stopped watching \"{keyword}\"");
+    en.insert(
+        "watch_list_empty",
+        "You're not watching any keywords yet. Use /watch <keyword> to add one.",
+    );
+    en.insert("watch_list", "Watching: {keywords}");
+    en.insert("language_usage", "Usage: /language <code>");
+    en.insert("language_unknown", "Unsupported language \"{lang}\". Supported: {langs}");
+    en.insert("language_set", "Language set to {lang}");
+    en.insert(
+        "language_requires_subscription",
+        "You're not subscribed yet. Use /subscribe first, then set your language.",
+    );
+    en.insert("submission", "{title}\n{url}");
+    en.insert("submission_no_url", "{title}");
+    langs.insert("en", en);
+
+    let mut pt = HashMap::new();
+    pt.insert("subscribed", "Inscrito no mechmarket");
+    pt.insert("unsubscribed", "Inscrição cancelada no mechmarket");
+    pt.insert("watch_usage", "Uso: /watch <palavra-chave>");
+    pt.insert("watch_added", "Observando \"{keyword}\"");
+    pt.insert("unwatch_usage", "Uso: /unwatch <palavra-chave>");
+    pt.insert("watch_removed", "Parou de observar \"{keyword}\"");
+    pt.insert(
+        "watch_list_empty",
+        "Você ainda não está observando nenhuma palavra-chave. Use /watch <palavra-chave> para adicionar uma.",
+    );
+    pt.insert("watch_list", "Observando: {keywords}");
+    pt.insert("language_usage", "Uso: /language <código>");
+    pt.insert(
+        "language_unknown",
+        "Idioma \"{lang}\" não suportado. Suportados: {langs}",
+    );
+    pt.insert("language_set", "Idioma definido para {lang}");
+    pt.insert(
+        "language_requires_subscription",
+        "Você ainda não está inscrito. Use /subscribe primeiro e depois defina seu idioma.",
+    );
+    langs.insert("pt", pt);
+
+    langs
+});
+
+/// Whether `lang` has a translation table at all, i.e. is a language code
+/// the bot actually supports.
+pub fn is_supported(lang: &str) -> bool {
+    STRINGS.contains_key(lang)
+}
+
+/// The language codes the bot supports, for error messages.
+pub fn supported_langs() -> Vec<&'static str> {
+    let mut langs: Vec<&'static str> = STRINGS.keys().copied().collect();
+    langs.sort_unstable();
+    langs
+}
+
+/// Looks up `key` for `lang`, falling back to `en` when the language is
+/// unknown or the key hasn't been translated yet, and substitutes
+/// `{name}` placeholders from `args`.
+pub fn t(lang: &str, key: &str, args: &[(&str, &str)]) -> String {
+    let template = STRINGS
+        .get(lang)
+        .and_then(|strings| strings.get(key))
+        .or_else(|| STRINGS.get(DEFAULT_LANG).and_then(|strings| strings.get(key)))
+        .copied()
+        .unwrap_or(key);
+
+    let mut rendered = template.to_string();
+    for (name, value) in args {
+        rendered = rendered.replace(&format!("{{{}}}", name), value);
+    }
+    rendered
+}
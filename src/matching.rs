@@ -0,0 +1,143 @@
+use fancy_regex::Regex;
+
+/// A single term in a keyword pattern: either a plain substring or a
+/// `/regex/` compiled with `fancy_regex` (so lookarounds are available).
+enum Term {
+    Plain(String),
+    Regex(Regex),
+}
+
+/// One `AND`-joined clause of a keyword pattern, optionally negated with
+/// `NOT` (e.g. `NOT group-buy`).
+struct Clause {
+    negate: bool,
+    term: Term,
+}
+
+/// A compiled keyword, e.g. `"gmk AND NOT group-buy"` or `"/\bban\b/"`.
+pub struct Pattern {
+    clauses: Vec<Clause>,
+}
+
+impl Pattern {
+    /// Compiles a raw keyword string into ANDed (optionally negated)
+    /// clauses. Plain clauses match case-insensitive substrings; a clause
+    /// wrapped in `/.../` is compiled as a case-insensitive `fancy_regex`.
+    pub fn compile(raw: &str) -> anyhow::Result<Self> {
+        let clauses = raw
+            .split(" AND ")
+            .map(|part| {
+                let part = part.trim();
+                let (negate, part) = match part.strip_prefix("NOT ") {
+                    Some(rest) => (true, rest.trim()),
+                    None => (false, part),
+                };
+
+                let term = match part.strip_prefix('/').and_then(|s| s.strip_suffix('/')) {
+                    Some(body) => Term::Regex(Regex::new(&format!("(?i){}", body))?),
+                    None => Term::Plain(part.to_lowercase()),
+                };
+
+                Ok(Clause { negate, term })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        if clauses.iter().all(|clause| clause.negate) {
+            anyhow::bail!(
+                "pattern {:?} has no positive clause and would match almost every post",
+                raw
+            );
+        }
+
+        Ok(Pattern { clauses })
+    }
+
+    fn matches(&self, haystack: &str) -> bool {
+        let lower = haystack.to_lowercase();
+        self.clauses.iter().all(|clause| {
+            let hit = match &clause.term {
+                Term::Plain(needle) => lower.contains(needle.as_str()),
+                Term::Regex(re) => re.is_match(haystack).unwrap_or(false),
+            };
+            hit != clause.negate
+        })
+    }
+}
+
+/// Compiles every keyword in `keywords`, dropping (and logging) any that
+/// fail to compile rather than failing the whole watch list.
+pub fn compile_all(keywords: &[String]) -> Vec<Pattern> {
+    keywords
+        .iter()
+        .filter_map(|raw| match Pattern::compile(raw) {
+            Ok(pattern) => Some(pattern),
+            Err(e) => {
+                eprintln!("Error compiling keyword pattern {:?}: {:?}", raw, e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Whether any compiled pattern matches the post. Title and selftext are
+/// joined into a single document before matching, so a clause like
+/// `NOT group-buy` excludes a post with "group-buy" anywhere in it, not
+/// just when it shares a field with the positive clause.
+pub fn matches_any(patterns: &[Pattern], title: &str, selftext: &str) -> bool {
+    let document = format!("{title}\n{selftext}");
+    patterns.iter().any(|pattern| pattern.matches(&document))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pattern;
+
+    #[test]
+    fn plain_substring_is_case_insensitive() {
+        let pattern = Pattern::compile("gmk").unwrap();
+        assert!(pattern.matches("Selling a GMK keycap set"));
+        assert!(!pattern.matches("Selling a keycap set"));
+    }
+
+    #[test]
+    fn regex_supports_lookaround() {
+        // matches "buy" only when not preceded by "group-"
+        let pattern = Pattern::compile("/(?<!group-)buy/").unwrap();
+        assert!(pattern.matches("WTB: let's buy some switches"));
+        assert!(!pattern.matches("group-buy for the keycaps"));
+    }
+
+    #[test]
+    fn and_requires_every_clause() {
+        let pattern = Pattern::compile("gmk AND keycaps").unwrap();
+        assert!(pattern.matches("gmk keycaps for sale"));
+        assert!(!pattern.matches("gmk switches for sale"));
+    }
+
+    #[test]
+    fn not_excludes_matching_posts() {
+        let pattern = Pattern::compile("gmk AND NOT group-buy").unwrap();
+        assert!(pattern.matches("gmk keycaps for sale"));
+        assert!(!pattern.matches("gmk keycaps group-buy"));
+    }
+
+    #[test]
+    fn all_negated_pattern_is_rejected() {
+        assert!(Pattern::compile("NOT group-buy").is_err());
+    }
+
+    #[test]
+    fn not_excludes_across_title_and_selftext() {
+        let patterns = vec![Pattern::compile("gmk AND NOT group-buy").unwrap()];
+        assert!(super::matches_any(
+            &patterns,
+            "gmk keycaps for sale",
+            "shipping worldwide"
+        ));
+        assert!(!super::matches_any(
+            &patterns,
+            "gmk keycaps for sale",
+            "this is a group-buy"
+        ));
+    }
+}